@@ -6,31 +6,61 @@
 //!
 //! This crate contains a basic macro which imitates lazy-static, but also produces a function
 //! which can eagerly load all static variables defined in that macro block.
+//!
+//! Enable the `spin_no_std` feature to generate statics backed by `spin::Once` instead of
+//! `std::sync::Once`, so the macro can be used in `#![no_std]` binaries. This requires the
+//! `spin` crate to be available wherever `eazy_static!` is invoked. The generated
+//! `init_all_status`/`init_<name>_status` function returns `alloc::vec::Vec`, so callers building
+//! under `spin_no_std` also need `extern crate alloc;` and a `#[global_allocator]` in scope.
 
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TS2;
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned};
 use syn::parse::{Parse, ParseStream, Result};
 use syn::spanned::Spanned;
 use syn::{parse_macro_input, Attribute, Expr, Ident, Token, Type, Visibility};
 
+mod kw {
+    syn::custom_keyword!(group);
+}
+
 struct EazyStatic {
+    attrs: Vec<Attribute>,
     visibility: Visibility,
     name: Ident,
     ty: Type,
     init: Expr,
 }
 
+/// Header controlling the name and visibility of the generated eager-init function, written as
+/// `@group <visibility> <Name>;` at the top of an `eazy_static!` block.
+struct Group {
+    visibility: Visibility,
+    name: Ident,
+}
+
 struct EazyStatics {
+    group: Option<Group>,
     statics: Vec<EazyStatic>,
 }
 
 impl Parse for EazyStatics {
     fn parse(input: ParseStream) -> Result<Self> {
+        let group = if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            input.parse::<kw::group>()?;
+            let visibility: Visibility = input.parse()?;
+            let name: Ident = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Some(Group { visibility, name })
+        } else {
+            None
+        };
+
         let mut statics = Vec::default();
         while !input.is_empty() {
-            Attribute::parse_outer(input)?;
+            let attrs = Attribute::parse_outer(input)?;
             let visibility: Visibility = input.parse()?;
             input.parse::<Token![static]>()?;
             input.parse::<Token![ref]>()?;
@@ -41,13 +71,14 @@ impl Parse for EazyStatics {
             let init: Expr = input.parse()?;
             input.parse::<Token![;]>()?;
             statics.push(EazyStatic {
+                attrs,
                 visibility,
                 name,
                 ty,
                 init,
             })
         }
-        Ok(Self { statics })
+        Ok(Self { group, statics })
     }
 }
 
@@ -58,6 +89,17 @@ impl Parse for EazyStatics {
 /// Wherever this macro is used, a public function `init_all` will be in scope, which when called
 /// access each of the variables defined in the macro block. This will initialise every variable
 /// that has not already been initialised.
+///
+/// An optional `@group <visibility> <Name>;` header may be placed at the top of the block to
+/// generate `init_<name>` (lower-cased) instead of `init_all`, with the given visibility. This
+/// allows multiple `eazy_static!` blocks to coexist in the same module without a name clash, e.g.
+/// `eazy_static! { @group pub(crate) CONFIG; static ref ... }` generates `pub(crate) fn
+/// init_config()`.
+///
+/// Each generated static also exposes `is_initialized()`, which reports whether `deref` has
+/// already run on it without triggering initialisation. A companion function is generated
+/// alongside the eager-init function (`init_all_status` / `init_<name>_status`) returning a
+/// `Vec` of `(name, is_initialized)` pairs for every static in the block.
 /// ```
 /// use eazy_static::eazy_static;
 ///
@@ -83,23 +125,48 @@ impl Parse for EazyStatics {
 /// assert!(X.load(Ordering::SeqCst));
 /// assert!(Y.load(Ordering::SeqCst));
 ///
+/// assert!(!XEDITED.is_initialized());
+/// assert!(!YEDITED.is_initialized());
+///
 /// println!("{}", *XEDITED);
 /// assert_eq!(X.load(Ordering::SeqCst), false);
 /// assert!(Y.load(Ordering::SeqCst));
+/// assert!(XEDITED.is_initialized());
+/// assert!(!YEDITED.is_initialized());
 ///
 /// init_all();
 /// assert_eq!(Y.load(Ordering::SeqCst), false);
+/// assert!(YEDITED.is_initialized());
+/// assert_eq!(
+///     init_all_status(),
+///     vec![("XEDITED", true), ("YEDITED", true)],
+/// );
+/// ```
+///
+/// A named, scoped group:
+/// ```
+/// use eazy_static::eazy_static;
+///
+/// eazy_static! {
+///     @group pub(crate) CONFIG;
+///     static ref NAME: &'static str = "eazy-static";
+/// }
+///
+/// assert_eq!(*NAME, "eazy-static");
+/// init_config();
 /// ```
 pub fn eazy_static(input: TokenStream) -> TokenStream {
-    let EazyStatics { statics } = parse_macro_input!(input as EazyStatics);
+    let EazyStatics { group, statics } = parse_macro_input!(input as EazyStatics);
 
     let mut iter = statics.iter();
 
     let mut out: TokenStream = TokenStream::default();
 
     let mut deref_all: TS2 = TS2::default();
+    let mut status_all: TS2 = TS2::default();
 
     while let Some(EazyStatic {
+        attrs,
         visibility,
         name,
         ty,
@@ -108,57 +175,140 @@ pub fn eazy_static(input: TokenStream) -> TokenStream {
     {
         if let Expr::Tuple(ref init) = init {
             if init.elems.is_empty() {
-                init.span().unwrap();
-                return TokenStream::new();
+                let error = syn::Error::new_spanned(init, "initializer expression cannot be `()`")
+                    .to_compile_error();
+                out.extend(TokenStream::from(error));
+                continue;
             }
         }
 
+        let core_crate = if cfg!(feature = "spin_no_std") {
+            quote! { core }
+        } else {
+            quote! { std }
+        };
+
         let assert_sync = quote_spanned! {ty.span()=>
-            struct _AssertSync where #ty: std::marker::Sync;
+            struct _AssertSync where #ty: #core_crate::marker::Sync;
         };
 
         let assert_sized = quote_spanned! {ty.span()=>
-            struct _AssertSized where #ty: std::marker::Sized;
+            struct _AssertSized where #ty: #core_crate::marker::Sized;
         };
 
-        let init_ptr = quote_spanned! {init.span()=>
-            Box::into_raw(Box::new(#init))
+        // The `Once` (and, on the std path, the value pointer) have to live as module-level
+        // statics rather than function-locals so that `is_initialized` can inspect the same
+        // cell that `deref` populates.
+        let once_ident = format_ident!("__{}_ONCE", name);
+
+        let (once_decl, deref_body, is_initialized_body) = if cfg!(feature = "spin_no_std") {
+            let once_decl = quote! {
+                #(#attrs)*
+                static #once_ident: spin::Once<#ty> = spin::Once::new();
+            };
+            let deref_body = quote! {
+                #assert_sync
+                #assert_sized
+
+                #once_ident.call_once(|| #init)
+            };
+            let is_initialized_body = quote! {
+                #once_ident.is_completed()
+            };
+            (once_decl, deref_body, is_initialized_body)
+        } else {
+            let value_ident = format_ident!("__{}_VALUE", name);
+            let init_ptr = quote_spanned! {init.span()=>
+                Box::into_raw(Box::new(#init))
+            };
+            let once_decl = quote! {
+                #(#attrs)*
+                static #once_ident: std::sync::Once = std::sync::Once::new();
+                #(#attrs)*
+                static mut #value_ident: *mut #ty = 0 as *mut #ty;
+            };
+            let deref_body = quote! {
+                #assert_sync
+                #assert_sized
+
+                unsafe {
+                    #once_ident.call_once(|| #value_ident = #init_ptr);
+                    &*#value_ident
+                }
+            };
+            let is_initialized_body = quote! {
+                #once_ident.is_completed()
+            };
+            (once_decl, deref_body, is_initialized_body)
         };
 
         let expanded = quote! {
             #[allow(missing_copy_implementations)]
             #[allow(non_camel_case_types)]
             #[allow(dead_code)]
+            #(#attrs)*
             #visibility struct #name { __ : () }
+            #(#attrs)*
             #[doc(hidden)]
             #visibility static #name: #name = #name { __ : () };
+            #[doc(hidden)]
+            #once_decl
+
+            #(#attrs)*
+            impl #name {
+                /// Returns whether this static has already been initialised, without triggering
+                /// initialisation itself.
+                #visibility fn is_initialized(&self) -> bool {
+                    #is_initialized_body
+                }
+            }
 
-            impl std::ops::Deref for #name {
+            #(#attrs)*
+            impl #core_crate::ops::Deref for #name {
                 type Target = #ty;
 
                 fn deref(&self) -> &#ty {
-                    #assert_sync
-                    #assert_sized
-
-                    static ONCE: std::sync::Once = std::sync::Once::new();
-                    static mut VALUE: *mut #ty = 0 as *mut #ty;
-
-                    unsafe {
-                        ONCE.call_once(|| VALUE = #init_ptr);
-                        &*VALUE
-                    }
+                    #deref_body
                 }
             }
         };
         out.extend(TokenStream::from(expanded));
         deref_all.extend(quote! {
-            let _ = std::ops::Deref::deref(&#name);
-        })
+            #(#attrs)*
+            let _ = #core_crate::ops::Deref::deref(&#name);
+        });
+        status_all.extend(quote! {
+            #(#attrs)*
+            __status.push((stringify!(#name), #name.is_initialized()));
+        });
     }
+    let (init_fn_visibility, init_fn_name) = match &group {
+        Some(Group { visibility, name }) => (
+            quote! { #visibility },
+            format_ident!("init_{}", name.to_string().to_lowercase()),
+        ),
+        None => (quote! { pub }, format_ident!("init_all")),
+    };
+
+    let init_fn_status_name = format_ident!("{}_status", init_fn_name);
+    let vec_crate = if cfg!(feature = "spin_no_std") {
+        quote! { alloc::vec }
+    } else {
+        quote! { std::vec }
+    };
+
     let init_all = quote! {
-        pub fn init_all() {
+        #init_fn_visibility fn #init_fn_name() {
             #deref_all
         }
+
+        /// Reports, for each static declared in this block, whether `deref` has already run on
+        /// it. This does not itself trigger initialisation.
+        #init_fn_visibility fn #init_fn_status_name() -> #vec_crate::Vec<(&'static str, bool)> {
+            let mut __status = #vec_crate![];
+            #status_all
+            __status
+        }
     };
     out.extend(TokenStream::from(init_all));
     out
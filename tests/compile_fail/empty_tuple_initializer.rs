@@ -0,0 +1,10 @@
+use eazy_static::eazy_static;
+
+eazy_static! {
+    static ref BAD: &'static str = ();
+    static ref OK: &'static str = "still expands";
+}
+
+fn main() {
+    assert_eq!(*OK, "still expands");
+}
@@ -0,0 +1,23 @@
+//! Exercises the `spin_no_std` code generation path end-to-end under `#![no_std]`.
+//!
+//! Run with `cargo test --test spin_no_std --features spin_no_std --no-default-features`.
+
+#![cfg(feature = "spin_no_std")]
+#![no_std]
+
+extern crate alloc;
+
+use eazy_static::eazy_static;
+
+eazy_static! {
+    static ref GREETING: &'static str = "hello from spin::Once";
+}
+
+#[test]
+fn initializes_lazily_via_spin_once() {
+    assert!(!GREETING.is_initialized());
+    assert_eq!(*GREETING, "hello from spin::Once");
+    assert!(GREETING.is_initialized());
+    init_all();
+    assert_eq!(init_all_status(), alloc::vec![("GREETING", true)]);
+}
@@ -0,0 +1,9 @@
+//! `trybuild` harness for cases that must fail to compile with a specific diagnostic.
+//!
+//! Requires a `trybuild` dev-dependency. Run with `cargo test --test trybuild`.
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+}